@@ -1,14 +1,20 @@
-// TODO: Can we make this alloc-only?
-
-use std::alloc::{self, Layout};
-use std::error::Error;
-use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
-use std::marker::PhantomData;
-use std::mem;
-use std::ops::{Deref, DerefMut};
-use std::ptr::{self, NonNull};
-use std::slice;
-use std::str;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::alloc::{handle_alloc_error, Layout};
+use core::error::Error;
+use core::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Deref, DerefMut};
+use core::ptr::{self, NonNull};
+use core::slice;
+use core::str;
+use core::sync::atomic::{self, AtomicUsize, Ordering};
+
+use allocator_api2::alloc::{Allocator, Global};
+pub use allocator_api2::alloc::AllocError;
 
 // We want to have the null pointer optimisation but we also don't want to allocate for empty
 // slices. That means we need some pointer that denotes an empty slice that we recognize and won't
@@ -28,6 +34,101 @@ impl Display for TooLong {
 
 impl Error for TooLong { }
 
+/// Everything that can go wrong in [`OwnedSlice::try_new`] (and friends).
+///
+/// [`AllocError`] (never turned into a process abort here; only the non-`try_` constructors such
+/// as [`OwnedSlice::new`] and [`Str::new_in`] escalate it by calling [`handle_alloc_error`]) is
+/// re-exported from
+/// `allocator-api2` so it lines up with the [`Allocator`] trait used for the `_in` constructors.
+#[derive(Copy, Clone, Debug)]
+pub enum NewError {
+    TooLong,
+    AllocError,
+}
+
+impl From<TooLong> for NewError {
+    fn from(_: TooLong) -> Self {
+        NewError::TooLong
+    }
+}
+
+impl From<AllocError> for NewError {
+    fn from(_: AllocError) -> Self {
+        NewError::AllocError
+    }
+}
+
+impl Display for NewError {
+    fn fmt(&self, fmt: &mut Formatter) -> FmtResult {
+        match self {
+            NewError::TooLong => Display::fmt(&TooLong, fmt),
+            NewError::AllocError => Display::fmt(&AllocError, fmt),
+        }
+    }
+}
+
+impl Error for NewError { }
+
+/// A [`CStr`] was built from bytes that contain a NUL somewhere other than the terminator
+/// `CStr` itself appends.
+#[derive(Copy, Clone, Debug)]
+pub struct InteriorNul;
+
+impl Display for InteriorNul {
+    fn fmt(&self, fmt: &mut Formatter) -> FmtResult {
+        write!(fmt, "Interior NUL byte")
+    }
+}
+
+impl Error for InteriorNul { }
+
+/// Everything that can go wrong in [`CStr::new`] (and friends) that isn't an allocation failure.
+#[derive(Copy, Clone, Debug)]
+pub enum CStrError {
+    TooLong,
+    InteriorNul,
+}
+
+impl Display for CStrError {
+    fn fmt(&self, fmt: &mut Formatter) -> FmtResult {
+        match self {
+            CStrError::TooLong => Display::fmt(&TooLong, fmt),
+            CStrError::InteriorNul => Display::fmt(&InteriorNul, fmt),
+        }
+    }
+}
+
+impl Error for CStrError { }
+
+/// Everything that can go wrong in [`CStr::try_new`] (and friends).
+#[derive(Copy, Clone, Debug)]
+pub enum CStrNewError {
+    TooLong,
+    InteriorNul,
+    AllocError,
+}
+
+impl From<NewError> for CStrNewError {
+    fn from(err: NewError) -> Self {
+        match err {
+            NewError::TooLong => CStrNewError::TooLong,
+            NewError::AllocError => CStrNewError::AllocError,
+        }
+    }
+}
+
+impl Display for CStrNewError {
+    fn fmt(&self, fmt: &mut Formatter) -> FmtResult {
+        match self {
+            CStrNewError::TooLong => Display::fmt(&TooLong, fmt),
+            CStrNewError::InteriorNul => Display::fmt(&InteriorNul, fmt),
+            CStrNewError::AllocError => Display::fmt(&AllocError, fmt),
+        }
+    }
+}
+
+impl Error for CStrNewError { }
+
 pub unsafe trait Header {
     fn extra_needed(len: usize) -> Result<usize, TooLong>;
     unsafe fn encode_len(len: usize, extra: *mut u8) -> Self;
@@ -38,7 +139,8 @@ pub unsafe trait Header {
 
 pub struct BoxHeader(u8);
 
-// TODO: Variable length encoding
+// Variable length encoding lives in `VarIntHeader`, for when the 255-element cap here is too
+// tight.
 unsafe impl Header for BoxHeader {
     #[inline]
     fn extra_needed(len: usize) -> Result<usize, TooLong> {
@@ -67,17 +169,201 @@ unsafe impl Header for BoxHeader {
     }
 }
 
-pub struct OwnedSlice<T, H = BoxHeader>
+/// A [`Header`] that stores the length as a LEB128 varint, lifting the 255-element cap of
+/// [`BoxHeader`].
+///
+/// The first 7-bit group lives in the header byte itself, so slices under 128 elements still
+/// cost a single byte, same as `BoxHeader`; longer slices spill further groups into the `extra`
+/// bytes, one byte per additional 7 bits of length.
+pub struct VarIntHeader(u8);
+
+impl VarIntHeader {
+    /// How many 7-bit groups `len` needs, including the one folded into the header byte.
+    #[inline]
+    fn groups_for(len: usize) -> usize {
+        let mut rest = len >> 7;
+        let mut groups = 1;
+        while rest > 0 {
+            groups += 1;
+            rest >>= 7;
+        }
+        groups
+    }
+}
+
+unsafe impl Header for VarIntHeader {
+    #[inline]
+    fn extra_needed(len: usize) -> Result<usize, TooLong> {
+        // `groups_for` always terminates within `usize::BITS / 7 + 1` iterations, so this can't
+        // actually exceed what fits in `extra` on any real target; the `Result` exists so the
+        // trait stays uniform with `BoxHeader` and future headers with a real cap.
+        Ok(Self::groups_for(len) - 1)
+    }
+    #[inline]
+    unsafe fn encode_len(len: usize, extra: *mut u8) -> Self {
+        let groups = Self::groups_for(len);
+        let mut rest = len >> 7;
+        let header_byte = (len & 0x7f) as u8 | if groups > 1 { 0x80 } else { 0 };
+        for i in 0..groups - 1 {
+            let more = i + 1 < groups - 1;
+            let byte = (rest & 0x7f) as u8 | if more { 0x80 } else { 0 };
+            ptr::write(extra.add(i), byte);
+            rest >>= 7;
+        }
+        Self(header_byte)
+    }
+    #[inline]
+    unsafe fn decode_len(&self, extra: *const u8) -> usize {
+        let mut len = (self.0 & 0x7f) as usize;
+        if self.0 & 0x80 == 0 {
+            return len;
+        }
+        let mut shift = 7;
+        let mut i = 0;
+        loop {
+            let byte = *extra.add(i);
+            len |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+            i += 1;
+        }
+        len
+    }
+    #[inline]
+    fn inc(&self) -> bool {
+        false
+    }
+    #[inline]
+    fn dec(&self) -> bool {
+        true
+    }
+}
+
+/// A [`Header`] that does Arc-like sharing: `clone` is a pointer copy and an atomic increment,
+/// not a deep copy, and the allocation is freed once the last handle drops.
+///
+/// The refcount occupies the header byte(s) themselves, so (unlike `VarIntHeader`) the length is
+/// stored entirely in the `extra` bytes. `inc`/`dec` mirror `std::sync::Arc`'s orderings exactly:
+/// `Relaxed` increments (new handles are only ever derived from one we already hold) and a
+/// `Release` decrement followed by an `Acquire` fence on the side that drops to zero, so every
+/// write through any handle happens-before the final free.
+pub struct ArcHeader(AtomicUsize);
+
+// `std::process::abort` can't be named without `std`; `panic!` is the best a `#![no_std]` build
+// can do (it still terminates the refcount-overflowing thread rather than wrapping the counter).
+#[cold]
+#[cfg(feature = "std")]
+fn refcount_overflowed() -> ! {
+    std::process::abort()
+}
+
+#[cold]
+#[cfg(not(feature = "std"))]
+fn refcount_overflowed() -> ! {
+    panic!("ArcHeader refcount overflow")
+}
+
+impl ArcHeader {
+    #[inline]
+    fn extra_needed(len: usize) -> usize {
+        VarIntHeader::groups_for(len)
+    }
+}
+
+unsafe impl Header for ArcHeader {
+    #[inline]
+    fn extra_needed(len: usize) -> Result<usize, TooLong> {
+        Ok(Self::extra_needed(len))
+    }
+    #[inline]
+    unsafe fn encode_len(len: usize, extra: *mut u8) -> Self {
+        let groups = Self::extra_needed(len);
+        let mut rest = len;
+        for i in 0..groups {
+            let more = i + 1 < groups;
+            let byte = (rest & 0x7f) as u8 | if more { 0x80 } else { 0 };
+            ptr::write(extra.add(i), byte);
+            rest >>= 7;
+        }
+        Self(AtomicUsize::new(1))
+    }
+    #[inline]
+    unsafe fn decode_len(&self, extra: *const u8) -> usize {
+        let mut len = 0;
+        let mut shift = 0;
+        let mut i = 0;
+        loop {
+            let byte = *extra.add(i);
+            len |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+            i += 1;
+        }
+        len
+    }
+    #[inline]
+    fn inc(&self) -> bool {
+        // Mirror `Arc`'s overflow guard: an app that manages to create `isize::MAX` clones has a
+        // bug worth aborting over rather than silently wrapping the counter.
+        let old = self.0.fetch_add(1, Ordering::Relaxed);
+        if old > isize::MAX as usize {
+            refcount_overflowed();
+        }
+        true
+    }
+    #[inline]
+    fn dec(&self) -> bool {
+        if self.0.fetch_sub(1, Ordering::Release) != 1 {
+            return false;
+        }
+        atomic::fence(Ordering::Acquire);
+        true
+    }
+}
+
+pub struct OwnedSlice<T, H = BoxHeader, A = Global>
 where
     H: Header,
+    A: Allocator,
 {
     header: NonNull<H>,
     _data: PhantomData<T>,
+    _alloc: PhantomData<A>,
 }
 
-impl<T, H> OwnedSlice<T, H>
+/// Cleans up a partially-initialized allocation if `T::clone` panics while [`OwnedSlice`] is
+/// being constructed: drops the elements already written and frees the block. `mem::forget`
+/// this once construction finishes successfully.
+struct ConstructGuard<T, A: Allocator> {
+    base: NonNull<u8>,
+    layout: Layout,
+    data_ptr: *mut T,
+    alloc_ptr: *mut A,
+    written: usize,
+}
+
+impl<T, A: Allocator> Drop for ConstructGuard<T, A> {
+    fn drop(&mut self) {
+        unsafe {
+            for i in 0..self.written {
+                ptr::drop_in_place(self.data_ptr.add(i));
+            }
+            // The allocator instance lives inside the very block we're about to free; read it
+            // out first, same as `OwnedSlice`'s own `Drop`.
+            let alloc = ptr::read(self.alloc_ptr);
+            alloc.deallocate(self.base, self.layout);
+        }
+    }
+}
+
+impl<T, H, A> OwnedSlice<T, H, A>
 where
     H: Header,
+    A: Allocator,
 {
     #[inline]
     fn len(&self) -> usize {
@@ -97,15 +383,20 @@ where
         Layout::new::<H>().extend(Layout::array::<u8>(0).unwrap()).unwrap().1
     }
 
+    // The allocator instance lives *inside* the allocation, between the header (plus its
+    // variable-length extra bytes) and the data, so that the handle itself stays a single
+    // `NonNull<H>`. For a zero-sized `A` (e.g. `Global`) this adds nothing; for a stateful one
+    // the cost is borne by the allocation, not the handle.
     #[inline]
-    fn layout_and_offsets(len: usize) -> Result<(Layout, usize, usize), TooLong> {
+    fn layout_and_offsets(len: usize) -> Result<(Layout, usize, usize, usize), TooLong> {
         let extra = H::extra_needed(len)?;
         let (l1, len_off) = Layout::new::<H>()
             .extend(Layout::array::<u8>(extra).expect("Insanely large stuff"))
             .expect("Insanely large stuff");
+        let (l2, alloc_off) = l1.extend(Layout::new::<A>()).expect("Insanely large stuff");
         let data_layout = Layout::array::<T>(len).expect("Insanely large stuff");
-        let (layout, data_off) = l1.extend(data_layout).expect("Insanely large stuff");
-        Ok((layout, len_off, data_off))
+        let (layout, data_off) = l2.extend(data_layout).expect("Insanely large stuff");
+        Ok((layout, len_off, alloc_off, data_off))
     }
 
     #[inline]
@@ -114,10 +405,15 @@ where
     }
 
     #[inline]
-    fn data_offset(len: usize) -> usize {
+    fn alloc_offset(len: usize) -> usize {
         Self::layout_and_offsets(len).unwrap().2
     }
 
+    #[inline]
+    fn data_offset(len: usize) -> usize {
+        Self::layout_and_offsets(len).unwrap().3
+    }
+
     #[inline]
     fn data(&self, len: usize) -> *mut T {
         let offset = Self::data_offset(len);
@@ -126,53 +422,119 @@ where
         }
     }
 
+    #[inline]
+    fn alloc_ptr(&self, len: usize) -> *mut A {
+        let offset = Self::alloc_offset(len);
+        unsafe {
+            self.header.as_ptr().cast::<u8>().add(offset).cast::<A>()
+        }
+    }
+
     #[inline]
     fn is_sentinel(&self) -> bool {
         ptr::eq(self.header.as_ptr().cast::<u8>(), &ZERO_SENTINEL)
     }
+}
 
-    pub fn new(src: &[T]) -> Result<Self, TooLong>
+impl<T, H, A> OwnedSlice<T, H, A>
+where
+    H: Header,
+    A: Allocator,
+{
+    /// Like [`Self::new_in`], but surfaces allocator exhaustion as an error instead of aborting
+    /// the process.
+    ///
+    /// This is the constructor to reach for in `#![no_std]` or kernel-style contexts that must
+    /// survive OOM rather than calling [`handle_alloc_error`].
+    pub fn try_new_in(src: &[T], alloc: A) -> Result<Self, NewError>
     where
         T: Clone,
     {
         if src.is_empty() {
-            // Use the sentinel thing
+            // Use the sentinel thing; the allocator is dropped unused, same as a never-allocated
+            // arena would be.
             return Ok(Self::default());
         }
 
         let len = src.len();
-        let (layout, len_off, data_offset) = Self::layout_and_offsets(len)?;
+        let (layout, len_off, alloc_off, data_offset) = Self::layout_and_offsets(len)?;
         assert!(layout.size() > 0, "TODO: Handle 0 layout? Can it even happen?");
-        let ptr = unsafe { alloc::alloc(layout) };
-        if ptr.is_null() {
-            alloc::handle_alloc_error(layout);
-        }
+        let ptr = alloc.allocate(layout).map_err(NewError::from)?.as_ptr() as *mut u8;
         unsafe {
             let data_ptr = ptr.add(data_offset).cast::<T>();
             let len_ptr = ptr.add(len_off);
+            let alloc_ptr = ptr.add(alloc_off).cast::<A>();
             let hdr = ptr.cast::<H>();
 
             // Initialize everything
             ptr::write(hdr, H::encode_len(len, len_ptr));
+            ptr::write(alloc_ptr, alloc);
+
+            // If `T::clone` panics partway through, this guard's `Drop` runs the destructors of
+            // the elements already written and frees the block, instead of leaking both. It is
+            // defused with `mem::forget` once every element has been written successfully.
+            let mut guard = ConstructGuard {
+                base: NonNull::new_unchecked(ptr),
+                layout,
+                data_ptr,
+                alloc_ptr,
+                written: 0,
+            };
             for (idx, src) in src.iter().enumerate() {
-                // FIXME: Handle panics and release the memory/call destructors. Currently it is
-                // not UB, but we leak all the cloned things and the allocation. Not great.
                 ptr::write(data_ptr.add(idx), src.clone());
+                guard.written = idx + 1;
             }
+            mem::forget(guard);
 
             Ok(Self {
                 header: NonNull::new(hdr).unwrap(),
                 _data: PhantomData,
+                _alloc: PhantomData,
             })
         }
     }
 
+    /// Like [`Self::new`], but allocates from `alloc` instead of the default allocator.
+    pub fn new_in(src: &[T], alloc: A) -> Result<Self, TooLong>
+    where
+        T: Clone,
+    {
+        match Self::try_new_in(src, alloc) {
+            Ok(this) => Ok(this),
+            Err(NewError::TooLong) => Err(TooLong),
+            Err(NewError::AllocError) => handle_alloc_error(Self::layout(src.len())),
+        }
+    }
+
     // TODO: Some more constructors? Something without cloning?
 }
 
-impl<T, H> Drop for OwnedSlice<T, H>
+impl<T, H, A> OwnedSlice<T, H, A>
+where
+    H: Header,
+    A: Allocator + Default,
+{
+    /// Like [`Self::new`], but surfaces allocator exhaustion as an error instead of aborting
+    /// the process.
+    pub fn try_new(src: &[T]) -> Result<Self, NewError>
+    where
+        T: Clone,
+    {
+        Self::try_new_in(src, A::default())
+    }
+
+    pub fn new(src: &[T]) -> Result<Self, TooLong>
+    where
+        T: Clone,
+    {
+        Self::new_in(src, A::default())
+    }
+}
+
+impl<T, H, A> Drop for OwnedSlice<T, H, A>
 where
     H: Header,
+    A: Allocator,
 {
     fn drop(&mut self) {
         if self.is_sentinel() {
@@ -191,32 +553,44 @@ where
                     }
                 }
 
-                alloc::dealloc(self.header.as_ptr().cast::<u8>(), layout);
+                // Read the allocator out of the block before freeing it; it must not be
+                // dereferenced (or dropped in place) once the deallocation below happens.
+                let alloc = ptr::read(self.alloc_ptr(len));
+                let base = NonNull::new_unchecked(self.header.as_ptr().cast::<u8>());
+                alloc.deallocate(base, layout);
             }
         }
     }
 }
 
-impl<T, H> Clone for OwnedSlice<T, H>
+impl<T, H, A> Clone for OwnedSlice<T, H, A>
 where
     H: Header,
+    A: Allocator + Clone,
     T: Clone,
 {
     fn clone(&self) -> Self {
-        if !self.is_sentinel() && unsafe { self.header.as_ref().inc() } {
+        if self.is_sentinel() {
+            return Self::default();
+        }
+
+        if unsafe { self.header.as_ref().inc() } {
             Self {
                 header: self.header,
                 _data: PhantomData,
+                _alloc: PhantomData,
             }
         } else {
-            Self::new(self.deref()).expect("Already have layout for this size")
+            let alloc = unsafe { (*self.alloc_ptr(self.len())).clone() };
+            Self::new_in(self.deref(), alloc).expect("Already have layout for this size")
         }
     }
 }
 
-impl<T, H> Deref for OwnedSlice<T, H>
+impl<T, H, A> Deref for OwnedSlice<T, H, A>
 where
     H: Header,
+    A: Allocator,
 {
     type Target = [T];
 
@@ -233,7 +607,10 @@ where
     }
 }
 
-impl<T> DerefMut for OwnedSlice<T, BoxHeader> {
+impl<T, A> DerefMut for OwnedSlice<T, BoxHeader, A>
+where
+    A: Allocator,
+{
     #[inline]
     fn deref_mut(&mut self) -> &mut Self::Target {
         if self.is_sentinel() {
@@ -247,9 +624,34 @@ impl<T> DerefMut for OwnedSlice<T, BoxHeader> {
     }
 }
 
-impl<T, H> Debug for OwnedSlice<T, H>
+impl<T, A> OwnedSlice<T, ArcHeader, A>
+where
+    T: Clone,
+    A: Allocator + Clone,
+{
+    /// Returns a mutable view, cloning the underlying allocation first if it is currently
+    /// shared with other handles (refcount > 1).
+    pub fn make_mut(&mut self) -> &mut [T] {
+        if self.is_sentinel() {
+            return &mut [];
+        }
+
+        if unsafe { self.header.as_ref().0.load(Ordering::Acquire) } > 1 {
+            let alloc = unsafe { (*self.alloc_ptr(self.len())).clone() };
+            *self = Self::new_in(self.deref(), alloc).expect("Already have layout for this size");
+        }
+
+        let len = self.len();
+        unsafe {
+            slice::from_raw_parts_mut(self.data(len), len)
+        }
+    }
+}
+
+impl<T, H, A> Debug for OwnedSlice<T, H, A>
 where
     H: Header,
+    A: Allocator,
     T: Debug,
 {
     fn fmt(&self, fmt: &mut Formatter) -> FmtResult {
@@ -257,65 +659,114 @@ where
     }
 }
 
-impl<T, H> Default for OwnedSlice<T, H>
+impl<T, H, A> Default for OwnedSlice<T, H, A>
 where
     H: Header,
+    A: Allocator,
 {
     fn default() -> Self {
         Self {
             header: NonNull::new((&ZERO_SENTINEL as *const u8 as *mut u8).cast()).unwrap(),
             _data: PhantomData,
+            _alloc: PhantomData,
         }
     }
 }
 
 // With some headers, we do Arc-like sharing of stuff. Therefore we need to be conservative about
 // these and require both Send + Sync as the bounds, just like Arc.
-unsafe impl<T, H> Send for OwnedSlice<T, H>
+unsafe impl<T, H, A> Send for OwnedSlice<T, H, A>
 where
     H: Header + Send + Sync,
     T: Send + Sync,
+    A: Allocator + Send + Sync,
 {}
 
-unsafe impl<T, H> Sync for OwnedSlice<T, H>
+unsafe impl<T, H, A> Sync for OwnedSlice<T, H, A>
 where
     H: Header + Send + Sync,
     T: Send + Sync,
+    A: Allocator + Send + Sync,
 {}
 
-#[derive(Clone, Default)]
-pub struct Str<H: Header = BoxHeader>(OwnedSlice<u8, H>);
+pub struct Str<H: Header = BoxHeader, A: Allocator = Global>(OwnedSlice<u8, H, A>);
+
+impl<H, A> Clone for Str<H, A>
+where
+    H: Header,
+    A: Allocator + Clone,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
 
-impl<H> Str<H>
+impl<H, A> Default for Str<H, A>
 where
     H: Header,
+    A: Allocator,
+{
+    fn default() -> Self {
+        Self(OwnedSlice::default())
+    }
+}
+
+impl<H, A> Str<H, A>
+where
+    H: Header,
+    A: Allocator,
+{
+    /// Like [`Self::new_in`], but surfaces allocator exhaustion as an error instead of aborting
+    /// the process.
+    pub fn try_new_in(s: &str, alloc: A) -> Result<Self, NewError> {
+        OwnedSlice::try_new_in(s.as_bytes(), alloc).map(Self)
+    }
+
+    pub fn new_in(s: &str, alloc: A) -> Result<Self, TooLong> {
+        OwnedSlice::new_in(s.as_bytes(), alloc).map(Self)
+    }
+}
+
+impl<H, A> Str<H, A>
+where
+    H: Header,
+    A: Allocator + Default,
 {
     pub fn new(s: &str) -> Result<Self, TooLong> {
         OwnedSlice::new(s.as_bytes()).map(Self)
     }
+
+    /// Like [`Self::new`], but surfaces allocator exhaustion as an error instead of aborting
+    /// the process.
+    pub fn try_new(s: &str) -> Result<Self, NewError> {
+        OwnedSlice::try_new(s.as_bytes()).map(Self)
+    }
 }
 
-impl<H> Debug for Str<H>
+impl<H, A> Debug for Str<H, A>
 where
     H: Header,
+    A: Allocator,
 {
     fn fmt(&self, fmt: &mut Formatter) -> FmtResult {
         write!(fmt, "{:?}", self.deref())
     }
 }
 
-impl<H> Display for Str<H>
+impl<H, A> Display for Str<H, A>
 where
     H: Header,
+    A: Allocator,
 {
     fn fmt(&self, fmt: &mut Formatter) -> FmtResult {
         write!(fmt, "{}", self.deref())
     }
 }
 
-impl<H> Deref for Str<H>
+impl<H, A> Deref for Str<H, A>
 where
     H: Header,
+    A: Allocator,
 {
     type Target = str;
 
@@ -325,17 +776,128 @@ where
     }
 }
 
-impl DerefMut for Str<BoxHeader> {
+impl<A> DerefMut for Str<BoxHeader, A>
+where
+    A: Allocator,
+{
     fn deref_mut(&mut self) -> &mut Self::Target {
         // It was created from str originally
         unsafe { str::from_utf8_unchecked_mut(&mut self.0) }
     }
 }
 
-// TODO: CStr and other wrappers
+/// A byte string owned the same way as [`OwnedSlice`], but guaranteed NUL-terminated with no
+/// interior NUL bytes, so it derefs to a borrowed [`core::ffi::CStr`] and hands out a bare
+/// pointer for FFI without `CString`'s fatter representation (an owned `Vec` plus a `CString`
+/// wrapper around it).
+pub struct CStr<H: Header = BoxHeader, A: Allocator = Global>(OwnedSlice<u8, H, A>);
+
+impl<H, A> Clone for CStr<H, A>
+where
+    H: Header,
+    A: Allocator + Clone,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<H, A> CStr<H, A>
+where
+    H: Header,
+    A: Allocator,
+{
+    /// Like [`Self::new_in`], but surfaces allocator exhaustion as an error instead of aborting
+    /// the process.
+    pub fn try_new_in(bytes: &[u8], alloc: A) -> Result<Self, CStrNewError> {
+        if bytes.contains(&0) {
+            return Err(CStrNewError::InteriorNul);
+        }
+
+        let mut with_nul = alloc::vec::Vec::with_capacity(bytes.len() + 1);
+        with_nul.extend_from_slice(bytes);
+        with_nul.push(0);
+        OwnedSlice::try_new_in(&with_nul, alloc).map(Self).map_err(CStrNewError::from)
+    }
+
+    pub fn new_in(bytes: &[u8], alloc: A) -> Result<Self, CStrError> {
+        match Self::try_new_in(bytes, alloc) {
+            Ok(this) => Ok(this),
+            Err(CStrNewError::TooLong) => Err(CStrError::TooLong),
+            Err(CStrNewError::InteriorNul) => Err(CStrError::InteriorNul),
+            Err(CStrNewError::AllocError) => {
+                handle_alloc_error(OwnedSlice::<u8, H, A>::layout(bytes.len() + 1))
+            }
+        }
+    }
+}
+
+impl<H, A> CStr<H, A>
+where
+    H: Header,
+    A: Allocator + Default,
+{
+    /// Like [`Self::new`], but surfaces allocator exhaustion as an error instead of aborting
+    /// the process.
+    pub fn try_new(bytes: &[u8]) -> Result<Self, CStrNewError> {
+        Self::try_new_in(bytes, A::default())
+    }
+
+    pub fn new(bytes: &[u8]) -> Result<Self, CStrError> {
+        Self::new_in(bytes, A::default())
+    }
+
+    /// Like [`Self::new`], but rejects interior NULs in a `&str` rather than a `&[u8]`.
+    #[allow(clippy::should_implement_trait)] // mirrors `CString`'s inherent `from_str`-shaped helpers, not `str::FromStr`
+    pub fn from_str(s: &str) -> Result<Self, CStrError> {
+        Self::new(s.as_bytes())
+    }
+}
+
+impl<H, A> CStr<H, A>
+where
+    H: Header,
+    A: Allocator,
+{
+    /// A pointer to the NUL-terminated bytes, for cheap handoff across an FFI boundary.
+    pub fn as_ptr(&self) -> *const core::ffi::c_char {
+        self.0.deref().as_ptr().cast()
+    }
+}
+
+impl<H, A> Deref for CStr<H, A>
+where
+    H: Header,
+    A: Allocator,
+{
+    type Target = core::ffi::CStr;
+
+    fn deref(&self) -> &core::ffi::CStr {
+        // It was constructed with exactly one, trailing NUL and no interior ones.
+        unsafe { core::ffi::CStr::from_bytes_with_nul_unchecked(self.0.deref()) }
+    }
+}
+
+impl<H, A> Debug for CStr<H, A>
+where
+    H: Header,
+    A: Allocator,
+{
+    fn fmt(&self, fmt: &mut Formatter) -> FmtResult {
+        self.deref().fmt(fmt)
+    }
+}
+
+// TODO: Other wrappers
 
 #[cfg(test)]
 mod tests {
+    // The test harness always links `std`, regardless of whether the library itself is built
+    // `no_std`; pull its prelude back in so the existing `String`/`vec!`/`format!`-based tests
+    // don't need touching.
+    extern crate std;
+    use std::prelude::v1::*;
+
     use super::*;
 
     /// Check we have the null-pointer optimisation.
@@ -386,6 +948,132 @@ mod tests {
         OwnedSlice::<_>::new(&long).unwrap_err();
     }
 
+    /// `try_new` reports the same error as `new`, just without aborting.
+    #[test]
+    fn try_new_too_long() {
+        let long = vec![0u8; 300];
+        assert!(matches!(
+            OwnedSlice::<_>::try_new(&long).unwrap_err(),
+            NewError::TooLong,
+        ));
+    }
+
+    /// The allocator-threading constructors behave the same as the default ones when given
+    /// `Global` explicitly.
+    #[test]
+    fn new_in_global() {
+        let s = OwnedSlice::<String, BoxHeader, Global>::new_in(
+            &["Hello".to_owned(), "World".to_owned()],
+            Global,
+        ).unwrap();
+        assert_eq!(2, s.len());
+        let s2 = s.clone();
+        assert_eq!(s.deref(), s2.deref());
+    }
+
+    /// `VarIntHeader` round-trips lengths across the 1-, 2- and 5-byte group boundaries.
+    #[test]
+    fn varint_header_round_trip() {
+        for &len in &[0, 1, 127, 128, 300, 16_383, 16_384, 1 << 28, (1 << 28) + 5] {
+            let extra = VarIntHeader::extra_needed(len).unwrap();
+            let mut buf = vec![0u8; extra];
+            let header = unsafe { VarIntHeader::encode_len(len, buf.as_mut_ptr()) };
+            assert_eq!(len, unsafe { header.decode_len(buf.as_ptr()) }, "len = {len}");
+        }
+    }
+
+    /// A slice using `VarIntHeader` is no longer capped at 255 elements.
+    #[test]
+    fn varint_header_lifts_the_cap() {
+        let long = vec![0u8; 300];
+        let s = OwnedSlice::<_, VarIntHeader>::new(&long).unwrap();
+        assert_eq!(300, s.len());
+    }
+
+    /// `clone` on an `ArcHeader` slice is a pointer copy, not a deep copy.
+    #[test]
+    fn arc_header_clone_shares_allocation() {
+        let s = OwnedSlice::<i32, ArcHeader>::new(&[1, 2, 3]).unwrap();
+        let s2 = s.clone();
+        assert_eq!(s.data(s.len()), s2.data(s2.len()));
+        assert_eq!(&*s2, &[1, 2, 3]);
+    }
+
+    /// Clones dropped from several threads don't race or double-free; run under Miri with
+    /// `-Zmiri-preemption-rate` to shake out ordering bugs.
+    #[test]
+    fn arc_header_dropped_from_many_threads() {
+        let original = OwnedSlice::<i32, ArcHeader>::new(&[1, 2, 3]).unwrap();
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let s = original.clone();
+                std::thread::spawn(move || {
+                    assert_eq!(&*s, &[1, 2, 3]);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(&*original, &[1, 2, 3]);
+    }
+
+    /// `make_mut` on a shared allocation clones it first, leaving the other handle untouched.
+    #[test]
+    fn arc_header_make_mut_clones_on_write() {
+        let mut s = OwnedSlice::<i32, ArcHeader>::new(&[1, 2, 3]).unwrap();
+        let s2 = s.clone();
+        s.make_mut()[0] = 9;
+        assert_eq!(&*s, &[9, 2, 3]);
+        assert_eq!(&*s2, &[1, 2, 3]);
+    }
+
+    /// If `T::clone` panics partway through construction, the elements already cloned get
+    /// dropped and the allocation is freed, instead of both leaking. Run under Miri to confirm.
+    #[test]
+    fn panicking_clone_does_not_leak() {
+        use std::cell::Cell;
+        use std::panic::{self, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        struct Tracked {
+            clones: Rc<Cell<usize>>,
+            drops: Rc<Cell<usize>>,
+        }
+
+        impl Clone for Tracked {
+            fn clone(&self) -> Self {
+                let n = self.clones.get();
+                self.clones.set(n + 1);
+                if n == 2 {
+                    panic!("boom on the third clone");
+                }
+                Tracked {
+                    clones: self.clones.clone(),
+                    drops: self.drops.clone(),
+                }
+            }
+        }
+
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                self.drops.set(self.drops.get() + 1);
+            }
+        }
+
+        let clones = Rc::new(Cell::new(0));
+        let drops = Rc::new(Cell::new(0));
+        let src: Vec<_> = (0..4)
+            .map(|_| Tracked { clones: clones.clone(), drops: drops.clone() })
+            .collect();
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| OwnedSlice::<_>::new(&src)));
+        assert!(result.is_err());
+        // The first two clones succeeded and were written into the (now freed) allocation; the
+        // guard must have dropped exactly those, and only those, before `src` itself is dropped.
+        assert_eq!(2, drops.get());
+    }
+
     #[test]
     fn strings() {
         let s: Str = Str::new("Hello").unwrap();
@@ -393,4 +1081,22 @@ mod tests {
         assert_eq!("Hello", s.to_string());
         assert_eq!("\"Hello\"", format!("{:?}", s));
     }
+
+    #[test]
+    fn cstrings() {
+        let s: CStr = CStr::from_str("Hello").unwrap();
+        assert_eq!(b"Hello\0", &s.0[..]);
+        assert_eq!(c"Hello", s.deref());
+        unsafe {
+            assert_eq!(b'H', *s.as_ptr().cast::<u8>());
+        }
+    }
+
+    #[test]
+    fn cstrings_reject_interior_nul() {
+        assert!(matches!(
+            CStr::<BoxHeader>::new(b"Hel\0lo").unwrap_err(),
+            CStrError::InteriorNul,
+        ));
+    }
 }